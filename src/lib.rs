@@ -1,11 +1,13 @@
+use std::collections::VecDeque;
 use tree_sitter::{Parser, TreeCursor, Node, Tree};
 
-/// Order to iterate through the tree; for n-ary trees only
-/// Pre-order and Post-order make sense
+/// Order to iterate through the tree; for n-ary trees, Pre-order,
+/// Post-order and Breadth-first (level-order) all make sense
 #[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
 pub enum Order {
     Pre,
-    Post
+    Post,
+    Breadth
 }
 
 /// Iterative traversal of the tree; serves as a reference for both
@@ -88,13 +90,43 @@ fn traverse_helper<'a, F>(c: &mut TreeCursor<'a>, order: Order, cb: &mut F) wher
     }
 }
 
+/// Like `TreeCursor::goto_parent`, but treats `root_id` as if it had no
+/// parent, so a traversal seeded from `PreorderTraverse::from_node`/
+/// `PostorderTraverse::from_node` confines itself to that node's subtree
+/// instead of escaping into its siblings or ancestors.
+fn goto_parent_bounded(c: &mut TreeCursor, root_id: usize) -> bool {
+    if c.node().id() == root_id {
+        false
+    } else {
+        c.goto_parent()
+    }
+}
+
+/// Like `TreeCursor::goto_next_sibling`, but treats `root_id` as if it had
+/// no sibling, so a traversal confined by `goto_parent_bounded` also can't
+/// escape by stepping sideways out of the subtree the moment the cursor is
+/// back on the starting node itself (whether that's on the very first call,
+/// for a leaf start node, or after retracing all the way back up to it).
+fn goto_next_sibling_bounded(c: &mut TreeCursor, root_id: usize) -> bool {
+    if c.node().id() == root_id {
+        false
+    } else {
+        c.goto_next_sibling()
+    }
+}
+
 struct PreorderTraverse<'a> {
     cursor: Option<TreeCursor<'a>>,
+    root_id: usize,
 }
 
 impl<'a> PreorderTraverse<'a> {
     pub fn new(tree: &'a Tree) -> Self {
-        PreorderTraverse { cursor: Some(tree.walk()) }
+        Self::from_node(tree.root_node())
+    }
+
+    pub fn from_node(node: Node<'a>) -> Self {
+        PreorderTraverse { root_id: node.id(), cursor: Some(node.walk()) }
     }
 }
 
@@ -102,6 +134,7 @@ impl<'a> Iterator for PreorderTraverse<'a> {
     type Item = Node<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let root_id = self.root_id;
         let c = match self.cursor.as_mut() {
             None => {return None;}
             Some(c) => c
@@ -114,22 +147,26 @@ impl<'a> Iterator for PreorderTraverse<'a> {
         let node = c.node();
 
         // First, try to go to a child or a sibling; if either succeed, this will be the
-        // first time we touch that node, so it'll be the next starting node
-        if c.goto_first_child() || c.goto_next_sibling() {
+        // first time we touch that node, so it'll be the next starting node. The sibling
+        // step is bounded so a leaf start node isn't treated as having a sibling outside
+        // its own subtree.
+        if c.goto_first_child() || goto_next_sibling_bounded(c, root_id) {
             return Some(node);
         }
 
         loop {
             // If we can't go to the parent, then that means we've reached the root, and our
             // iterator will be done in the next iteration
-            if !c.goto_parent() {
+            if !goto_parent_bounded(c, root_id) {
                 self.cursor = None;
                 break;
             }
 
             // If we get to a sibling, then this will be the first time we touch that node,
-            // so it'll be the next starting node
-            if c.goto_next_sibling() {
+            // so it'll be the next starting node. Bounded for the same reason as above:
+            // retracing can land back on the start node itself, which must not have a
+            // sibling step out of the subtree.
+            if goto_next_sibling_bounded(c, root_id) {
                 break;
             }
         }
@@ -141,13 +178,19 @@ impl<'a> Iterator for PreorderTraverse<'a> {
 
 struct PostorderTraverse<'a> {
     cursor: Option<TreeCursor<'a>>,
-    retracing: bool
+    retracing: bool,
+    root_id: usize,
 }
 
 impl<'a> PostorderTraverse<'a> {
     pub fn new(tree: &'a Tree) -> Self {
+        Self::from_node(tree.root_node())
+    }
+
+    pub fn from_node(node: Node<'a>) -> Self {
         PostorderTraverse {
-            cursor: Some(tree.walk()),
+            root_id: node.id(),
+            cursor: Some(node.walk()),
             retracing: false
         }
     }
@@ -157,6 +200,7 @@ impl<'a> Iterator for PostorderTraverse<'a> {
     type Item = Node<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let root_id = self.root_id;
         let c = match self.cursor.as_mut() {
             None => {return None;}
             Some(c) => c
@@ -173,7 +217,7 @@ impl<'a> Iterator for PostorderTraverse<'a> {
         // We know this will be the last time we touch this node, as we will either be going
         // to its next sibling or retracing back up the tree
         let node = c.node();
-        if c.goto_next_sibling() {
+        if goto_next_sibling_bounded(c, root_id) {
             // If we successfully go to a sibling of this node, we want to go back down
             // the tree on the next iteration
             self.retracing = false;
@@ -181,7 +225,7 @@ impl<'a> Iterator for PostorderTraverse<'a> {
             // If we weren't already retracing, we are now; travel upwards until we can
             // go to the next sibling or reach the root again
             self.retracing = true;
-            if !c.goto_parent() {
+            if !goto_parent_bounded(c, root_id) {
                 // We've reached the root again, and our iteration is done
                 self.cursor = None;
             }
@@ -191,20 +235,207 @@ impl<'a> Iterator for PostorderTraverse<'a> {
     }
 }
 
+/// Control flow returned from the callbacks passed to `traverse_with`,
+/// mirroring Mozilla's `ForEachNode`: `Continue` proceeds as normal,
+/// `Skip` prevents descending into that node's children (and its
+/// `post_action` is not called either), and `Abort` unwinds and
+/// terminates the whole traversal immediately.
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub enum TraversalFlag {
+    Skip,
+    Continue,
+    Abort,
+}
+
+/// Traverse `tree`, calling `pre_action` when a node is first entered and
+/// `post_action` when retracing past it, with each action's `TraversalFlag`
+/// steering the traversal. Built on the same iterative `TreeCursor` loop as
+/// `traverse_iterative`, rather than the recursive helper, to preserve the
+/// crate's no-stack-overflow guarantee.
+pub fn traverse_with<'a, F, G>(tree: &'a Tree, mut pre_action: F, mut post_action: G)
+    where F: FnMut(Node<'a>) -> TraversalFlag, G: FnMut(Node<'a>) -> TraversalFlag
+{
+    let mut c = tree.walk();
+    loop {
+        let node = c.node();
+        let flag = pre_action(node);
+        if flag == TraversalFlag::Abort {
+            return;
+        }
+
+        // Only descend (and later give a post_action) if we were told to
+        // Continue and the node actually has children to descend into.
+        if flag == TraversalFlag::Continue && c.goto_first_child() {
+            continue;
+        }
+
+        if flag == TraversalFlag::Continue {
+            // A leaf we were allowed to enter: this is the only time we'll
+            // see it, so call its post_action now.
+            if post_action(node) == TraversalFlag::Abort {
+                return;
+            }
+        }
+
+        if c.goto_next_sibling() {
+            continue;
+        }
+
+        loop {
+            if !c.goto_parent() {
+                // Retraced all the way back past the root; we're done.
+                return;
+            }
+
+            // Any ancestor we retrace into here was necessarily Continue'd
+            // into (a Skip'd node is never descended past), so it always
+            // gets a post_action.
+            if post_action(c.node()) == TraversalFlag::Abort {
+                return;
+            }
+
+            if c.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+/// An edge encountered while traversing a tree in an Euler-tour-style
+/// walk: a node is entered once (`Start`) and, after all of its
+/// descendants have been visited, left once (`End`). Filtering for
+/// just `Start` or just `End` recovers preorder/postorder respectively,
+/// but seeing both in a single pass allows e.g. emitting matching
+/// open/close output for every node.
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub enum NodeEdge<'a> {
+    Start(Node<'a>),
+    End(Node<'a>),
+}
+
+/// Iterative Euler-tour traversal of the tree, yielding a `NodeEdge` for
+/// every time a node is entered or left, so each node appears exactly
+/// twice. Built on the same `TreeCursor` discipline as `PreorderTraverse`
+/// and `PostorderTraverse`: `retracing` tracks whether we're currently on
+/// our way back up the tree, looking for a node's next sibling or parent.
+struct NodeEdgeTraverse<'a> {
+    cursor: Option<TreeCursor<'a>>,
+    retracing: bool,
+}
+
+impl<'a> NodeEdgeTraverse<'a> {
+    pub fn new(tree: &'a Tree) -> Self {
+        NodeEdgeTraverse {
+            cursor: Some(tree.walk()),
+            retracing: false,
+        }
+    }
+}
+
+impl<'a> Iterator for NodeEdgeTraverse<'a> {
+    type Item = NodeEdge<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = match self.cursor.as_mut() {
+            None => {return None;}
+            Some(c) => c
+        };
+
+        // If we're not retracing, this is the first time we've touched the node,
+        // so we emit its Start; if it has no child to descend into, we're
+        // immediately done with it and will emit its End next time around.
+        if !self.retracing {
+            let node = c.node();
+            if !c.goto_first_child() {
+                self.retracing = true;
+            }
+            return Some(NodeEdge::Start(node));
+        }
+
+        // We're retracing, so this is the last time we'll touch this node;
+        // emit its End, then try to move to its next sibling (in which case
+        // we're done retracing) or back up to its parent (in which case we
+        // keep retracing so the parent's End gets emitted in turn).
+        let node = c.node();
+        if c.goto_next_sibling() {
+            self.retracing = false;
+        } else if !c.goto_parent() {
+            // We've retraced all the way back past the root, so we are done.
+            self.cursor = None;
+        }
+        Some(NodeEdge::End(node))
+    }
+}
+
+/// Traverse `tree`, yielding a [`NodeEdge::Start`] when a node is first
+/// entered and a [`NodeEdge::End`] when it is finished, so every node is
+/// seen exactly twice.
+pub fn traverse_edges(tree: &Tree) -> impl Iterator<Item=NodeEdge> {
+    NodeEdgeTraverse::new(tree)
+}
+
+/// Breadth-first (level-order) traversal of the tree. Unlike `PreorderTraverse`
+/// and `PostorderTraverse`, this can't be driven by a single `TreeCursor`, since
+/// tree-sitter's cursor is inherently depth-first; instead we keep a worklist of
+/// nodes still to visit, seeded with the root, and for each node we pop off the
+/// front we push all of its children (found by cursoring to the node and walking
+/// its siblings) onto the back.
+struct LevelorderTraverse<'a> {
+    worklist: VecDeque<Node<'a>>,
+}
+
+impl<'a> LevelorderTraverse<'a> {
+    pub fn new(tree: &'a Tree) -> Self {
+        Self::from_node(tree.root_node())
+    }
+
+    pub fn from_node(node: Node<'a>) -> Self {
+        let mut worklist = VecDeque::new();
+        worklist.push_back(node);
+        LevelorderTraverse { worklist }
+    }
+}
+
+impl<'a> Iterator for LevelorderTraverse<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.worklist.pop_front()?;
+
+        let mut c = node.walk();
+        if c.goto_first_child() {
+            loop {
+                self.worklist.push_back(c.node());
+                if !c.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+
+        Some(node)
+    }
+}
+
 struct Traverse<'a> {
     inner: TraverseInner<'a>
 }
 
 enum TraverseInner<'a> {
     Post(PostorderTraverse<'a>),
-    Pre(PreorderTraverse<'a>)
+    Pre(PreorderTraverse<'a>),
+    Breadth(LevelorderTraverse<'a>)
 }
 
 impl<'a> Traverse<'a> {
     pub fn new(tree: &'a Tree, order: Order) -> Self {
+        Self::from_node(tree.root_node(), order)
+    }
+
+    pub fn from_node(node: Node<'a>, order: Order) -> Self {
         let inner = match order {
-            Order::Pre => TraverseInner::Pre(PreorderTraverse::new(tree)),
-            Order::Post => TraverseInner::Post(PostorderTraverse::new(tree))
+            Order::Pre => TraverseInner::Pre(PreorderTraverse::from_node(node)),
+            Order::Post => TraverseInner::Post(PostorderTraverse::from_node(node)),
+            Order::Breadth => TraverseInner::Breadth(LevelorderTraverse::from_node(node))
         };
         Self { inner }
     }
@@ -214,17 +445,241 @@ pub fn traverse_iter(tree: &Tree, order: Order) -> impl Iterator<Item=Node> {
     return Traverse::new(tree, order);
 }
 
+/// Like `traverse_iter`, but confined to the subtree rooted at `node`
+/// rather than the whole tree: the traversal halts once it would retrace
+/// back above `node`, instead of continuing up into `node`'s siblings.
+pub fn traverse_node(node: Node, order: Order) -> impl Iterator<Item=Node> {
+    return Traverse::from_node(node, order);
+}
+
 impl<'a> Iterator for Traverse<'a> {
     type Item = Node<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.inner {
             TraverseInner::Post(ref mut i) => i.next(),
-            TraverseInner::Pre(ref mut i) => i.next()
+            TraverseInner::Pre(ref mut i) => i.next(),
+            TraverseInner::Breadth(ref mut i) => i.next()
         }
     }
 }
 
+/// A node visited during traversal, together with context that would
+/// otherwise require the caller to keep its own parallel stack: its
+/// `depth` relative to the traversal's starting node (which is 0), and
+/// the `field_name` (if any) by which it hangs off its parent. The
+/// starting node itself always has a `field_name` of `None`, since the
+/// traversal does not look above it.
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub struct TraversalItem<'a> {
+    pub node: Node<'a>,
+    pub depth: usize,
+    pub field_name: Option<&'static str>,
+}
+
+struct PreorderTraverseWithContext<'a> {
+    cursor: Option<TreeCursor<'a>>,
+    root_id: usize,
+    depth: usize,
+}
+
+impl<'a> PreorderTraverseWithContext<'a> {
+    pub fn new(tree: &'a Tree) -> Self {
+        Self::from_node(tree.root_node())
+    }
+
+    pub fn from_node(node: Node<'a>) -> Self {
+        PreorderTraverseWithContext { root_id: node.id(), cursor: Some(node.walk()), depth: 0 }
+    }
+}
+
+impl<'a> Iterator for PreorderTraverseWithContext<'a> {
+    type Item = TraversalItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let root_id = self.root_id;
+        let c = match self.cursor.as_mut() {
+            None => {return None;}
+            Some(c) => c
+        };
+
+        // Same discipline as PreorderTraverse, plus depth tracked incrementally
+        // as we descend/ascend, and the field name read at the node we're
+        // about to return before we potentially move off of it.
+        let item = TraversalItem { node: c.node(), depth: self.depth, field_name: c.field_name() };
+
+        if c.goto_first_child() {
+            self.depth += 1;
+            return Some(item);
+        }
+        // Bounded the same way as PreorderTraverse, so a leaf start node isn't
+        // treated as having a sibling outside its own subtree.
+        if goto_next_sibling_bounded(c, root_id) {
+            return Some(item);
+        }
+
+        loop {
+            if !goto_parent_bounded(c, root_id) {
+                self.cursor = None;
+                break;
+            }
+            self.depth -= 1;
+
+            if goto_next_sibling_bounded(c, root_id) {
+                break;
+            }
+        }
+
+        Some(item)
+    }
+}
+
+struct PostorderTraverseWithContext<'a> {
+    cursor: Option<TreeCursor<'a>>,
+    retracing: bool,
+    root_id: usize,
+    depth: usize,
+}
+
+impl<'a> PostorderTraverseWithContext<'a> {
+    pub fn new(tree: &'a Tree) -> Self {
+        Self::from_node(tree.root_node())
+    }
+
+    pub fn from_node(node: Node<'a>) -> Self {
+        PostorderTraverseWithContext {
+            root_id: node.id(),
+            cursor: Some(node.walk()),
+            retracing: false,
+            depth: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PostorderTraverseWithContext<'a> {
+    type Item = TraversalItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let root_id = self.root_id;
+        let c = match self.cursor.as_mut() {
+            None => {return None;}
+            Some(c) => c
+        };
+
+        if !self.retracing {
+            while c.goto_first_child() {
+                self.depth += 1;
+            }
+        }
+
+        let item = TraversalItem { node: c.node(), depth: self.depth, field_name: c.field_name() };
+        if goto_next_sibling_bounded(c, root_id) {
+            self.retracing = false;
+        } else {
+            self.retracing = true;
+            if !goto_parent_bounded(c, root_id) {
+                self.cursor = None;
+            } else {
+                self.depth -= 1;
+            }
+        }
+
+        Some(item)
+    }
+}
+
+/// Breadth-first traversal with context; since `LevelorderTraverse` doesn't
+/// keep a `TreeCursor` positioned on each pending node, depth and field name
+/// are computed once, when a node is pushed onto the worklist as a child of
+/// whatever node is currently being expanded, and carried alongside it.
+struct LevelorderTraverseWithContext<'a> {
+    worklist: VecDeque<(Node<'a>, usize, Option<&'static str>)>,
+}
+
+impl<'a> LevelorderTraverseWithContext<'a> {
+    pub fn new(tree: &'a Tree) -> Self {
+        Self::from_node(tree.root_node())
+    }
+
+    pub fn from_node(node: Node<'a>) -> Self {
+        let mut worklist = VecDeque::new();
+        worklist.push_back((node, 0, None));
+        LevelorderTraverseWithContext { worklist }
+    }
+}
+
+impl<'a> Iterator for LevelorderTraverseWithContext<'a> {
+    type Item = TraversalItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth, field_name) = self.worklist.pop_front()?;
+
+        let mut c = node.walk();
+        if c.goto_first_child() {
+            loop {
+                self.worklist.push_back((c.node(), depth + 1, c.field_name()));
+                if !c.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+
+        Some(TraversalItem { node, depth, field_name })
+    }
+}
+
+struct ContextTraverse<'a> {
+    inner: ContextTraverseInner<'a>
+}
+
+enum ContextTraverseInner<'a> {
+    Pre(PreorderTraverseWithContext<'a>),
+    Post(PostorderTraverseWithContext<'a>),
+    Breadth(LevelorderTraverseWithContext<'a>)
+}
+
+impl<'a> ContextTraverse<'a> {
+    pub fn new(tree: &'a Tree, order: Order) -> Self {
+        Self::from_node(tree.root_node(), order)
+    }
+
+    pub fn from_node(node: Node<'a>, order: Order) -> Self {
+        let inner = match order {
+            Order::Pre => ContextTraverseInner::Pre(PreorderTraverseWithContext::from_node(node)),
+            Order::Post => ContextTraverseInner::Post(PostorderTraverseWithContext::from_node(node)),
+            Order::Breadth => ContextTraverseInner::Breadth(LevelorderTraverseWithContext::from_node(node))
+        };
+        Self { inner }
+    }
+}
+
+impl<'a> Iterator for ContextTraverse<'a> {
+    type Item = TraversalItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner {
+            ContextTraverseInner::Pre(ref mut i) => i.next(),
+            ContextTraverseInner::Post(ref mut i) => i.next(),
+            ContextTraverseInner::Breadth(ref mut i) => i.next()
+        }
+    }
+}
+
+/// Like `traverse_iter`, but yields a `TraversalItem` carrying each node's
+/// depth and field name alongside it, so consumers don't have to keep
+/// their own parallel stack to recover that context.
+pub fn traverse_iter_with_context(tree: &Tree, order: Order) -> impl Iterator<Item=TraversalItem> {
+    return ContextTraverse::new(tree, order);
+}
+
+/// Like `traverse_node`, but yields a `TraversalItem` carrying each node's
+/// depth (relative to `node`, which is 0) and field name alongside it;
+/// `node` itself is reported with no field name, even if it has one in
+/// its parent.
+pub fn traverse_node_with_context(node: Node, order: Order) -> impl Iterator<Item=TraversalItem> {
+    return ContextTraverse::from_node(node, order);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,4 +740,169 @@ function double(x, y, z=123) {
         eprintln!("{:?}", v);
         "(program (function_declaration name: (identifier) parameters: (formal_parameters (identifier)) body: (statement_block)))";
     }
+
+    #[test]
+    fn edges_filter_to_preorder_and_postorder() {
+        let parsed = get_tree();
+
+        let starts: Vec<_> = traverse_edges(&parsed).filter_map(|edge| match edge {
+            NodeEdge::Start(node) => Some(node),
+            NodeEdge::End(_) => None,
+        }).collect();
+        let mut pre = Vec::new();
+        traverse_recursive(&parsed, Order::Pre, |node| {pre.push(node)});
+        assert_eq!(starts, pre);
+
+        let ends: Vec<_> = traverse_edges(&parsed).filter_map(|edge| match edge {
+            NodeEdge::End(node) => Some(node),
+            NodeEdge::Start(_) => None,
+        }).collect();
+        let mut post = Vec::new();
+        traverse_recursive(&parsed, Order::Post, |node| {post.push(node)});
+        assert_eq!(ends, post);
+    }
+
+    #[test]
+    fn traverse_with_skip_prunes_subtree() {
+        let parsed = get_tree();
+
+        let mut entered = Vec::new();
+        let mut left = Vec::new();
+        traverse_with(&parsed, |node| {
+            entered.push(node.kind());
+            if node.kind() == "statement_block" {
+                TraversalFlag::Skip
+            } else {
+                TraversalFlag::Continue
+            }
+        }, |node| {
+            left.push(node.kind());
+            TraversalFlag::Continue
+        });
+
+        assert!(entered.contains(&"statement_block"));
+        assert!(!entered.contains(&"return_statement"));
+        assert!(!left.contains(&"statement_block"));
+    }
+
+    #[test]
+    fn traverse_with_abort_stops_immediately() {
+        let parsed = get_tree();
+
+        let mut entered = Vec::new();
+        traverse_with(&parsed, |node| {
+            entered.push(node.kind());
+            if node.kind() == "function_declaration" {
+                TraversalFlag::Abort
+            } else {
+                TraversalFlag::Continue
+            }
+        }, |_node| TraversalFlag::Continue);
+
+        assert_eq!(entered.last(), Some(&"function_declaration"));
+    }
+
+    #[test]
+    fn breadth_visits_root_first_and_covers_all_nodes() {
+        let parsed = get_tree();
+
+        let breadth = traverse_iter(&parsed, Order::Breadth).collect::<Vec<_>>();
+        assert_eq!(breadth[0], parsed.root_node());
+
+        let mut pre = traverse_iter(&parsed, Order::Pre).collect::<Vec<_>>();
+        let mut breadth_sorted = breadth.clone();
+        pre.sort_by_key(|n| n.id());
+        breadth_sorted.sort_by_key(|n| n.id());
+        assert_eq!(breadth_sorted, pre);
+    }
+
+    #[test]
+    fn traverse_node_stays_within_subtree() {
+        let parsed = get_tree();
+
+        let body = traverse_iter(&parsed, Order::Pre)
+            .find(|node| node.kind() == "statement_block")
+            .expect("tree should contain a statement_block");
+
+        let pre: Vec<_> = traverse_node(body, Order::Pre).collect();
+        assert_eq!(pre[0], body);
+        assert!(pre.iter().all(|node| node.kind() != "lexical_declaration"));
+
+        let post: Vec<_> = traverse_node(body, Order::Post).collect();
+        assert_eq!(post.last(), Some(&body));
+        assert_eq!(post.len(), pre.len());
+    }
+
+    #[test]
+    fn traverse_node_leaf_with_siblings_stays_within_itself() {
+        let parsed = get_tree();
+
+        // `x, y, z=123` gives `formal_parameters` several named children, so its
+        // first child is a leaf with real siblings in the underlying tree.
+        let params = traverse_iter(&parsed, Order::Pre)
+            .find(|node| node.kind() == "formal_parameters")
+            .expect("tree should contain formal_parameters");
+        let first_param = params.named_child(0).expect("formal_parameters should have a first child");
+        assert_eq!(first_param.kind(), "identifier");
+        assert!(params.named_child(1).is_some(), "need at least two params for this regression test");
+
+        let pre: Vec<_> = traverse_node(first_param, Order::Pre).collect();
+        assert_eq!(pre, vec![first_param]);
+
+        let post: Vec<_> = traverse_node(first_param, Order::Post).collect();
+        assert_eq!(post, vec![first_param]);
+    }
+
+    #[test]
+    fn context_tracks_depth_and_field_name() {
+        let parsed = get_tree();
+
+        let items: Vec<_> = traverse_iter_with_context(&parsed, Order::Pre).collect();
+        let root = items[0];
+        assert_eq!(root.node, parsed.root_node());
+        assert_eq!(root.depth, 0);
+        assert_eq!(root.field_name, None);
+
+        let name_field = items.iter()
+            .find(|item| item.field_name == Some("name"))
+            .expect("function_declaration should have a name field");
+        assert_eq!(name_field.node.kind(), "identifier");
+        assert!(name_field.depth > root.depth);
+
+        let nodes: Vec<_> = items.iter().map(|item| item.node).collect();
+        assert_eq!(nodes, traverse_iter(&parsed, Order::Pre).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn traverse_node_with_context_leaf_with_siblings_stays_within_itself() {
+        let parsed = get_tree();
+
+        let params = traverse_iter(&parsed, Order::Pre)
+            .find(|node| node.kind() == "formal_parameters")
+            .expect("tree should contain formal_parameters");
+        let first_param = params.named_child(0).expect("formal_parameters should have a first child");
+        assert!(params.named_child(1).is_some(), "need at least two params for this regression test");
+
+        let pre: Vec<_> = traverse_node_with_context(first_param, Order::Pre).collect();
+        assert_eq!(pre.len(), 1);
+        assert_eq!(pre[0].node, first_param);
+        assert_eq!(pre[0].depth, 0);
+
+        let post: Vec<_> = traverse_node_with_context(first_param, Order::Post).collect();
+        assert_eq!(post.len(), 1);
+        assert_eq!(post[0].node, first_param);
+        assert_eq!(post[0].depth, 0);
+    }
+
+    #[test]
+    fn context_breadth_depth_is_nondecreasing() {
+        let parsed = get_tree();
+
+        let depths: Vec<_> = traverse_iter_with_context(&parsed, Order::Breadth)
+            .map(|item| item.depth)
+            .collect();
+        let mut sorted = depths.clone();
+        sorted.sort();
+        assert_eq!(depths, sorted);
+    }
 }
\ No newline at end of file